@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+/// A keyed, time-limited cache of raw Subsonic responses.
+///
+/// Sits in front of [`Client::get`](../client/struct.Client.html#method.get)
+/// so repeated calls to read-mostly endpoints (artist info, biography,
+/// cover art) don't round-trip to the server every time, as long as the
+/// cached value is younger than the configured TTL. Enabled via
+/// [`Client::with_cache`](../client/struct.Client.html#method.with_cache).
+#[derive(Debug, Default)]
+pub struct ResponseCache {
+    ttl: Option<Duration>,
+    entries: HashMap<(String, String), (Instant, Value)>,
+    byte_entries: HashMap<(String, String), (Instant, Vec<u8>)>,
+}
+
+impl ResponseCache {
+    /// Creates a cache that holds entries for `ttl` before they're
+    /// considered stale.
+    pub fn new(ttl: Duration) -> ResponseCache {
+        ResponseCache {
+            ttl: Some(ttl),
+            entries: HashMap::new(),
+            byte_entries: HashMap::new(),
+        }
+    }
+
+    /// Returns the cached value stored under `(endpoint, query)`, if one
+    /// exists and is still within the TTL.
+    pub fn get(&self, endpoint: &str, query: &str) -> Option<&Value> {
+        let ttl = self.ttl?;
+        let key = (endpoint.to_string(), query.to_string());
+
+        self.entries
+            .get(&key)
+            .filter(|&&(stored, _)| stored.elapsed() < ttl)
+            .map(|&(_, ref value)| value)
+    }
+
+    /// Stores `value` under `(endpoint, query)`, timestamped with now.
+    ///
+    /// Does nothing if the cache was never given a TTL.
+    pub fn insert(&mut self, endpoint: &str, query: &str, value: Value) {
+        if self.ttl.is_some() {
+            self.entries.insert(
+                (endpoint.to_string(), query.to_string()),
+                (Instant::now(), value),
+            );
+        }
+    }
+
+    /// Returns the cached bytes stored under `(endpoint, query)`, if any
+    /// exist and are still within the TTL.
+    ///
+    /// Used for large, immutable binary payloads such as cover art.
+    pub fn get_bytes(&self, endpoint: &str, query: &str) -> Option<&[u8]> {
+        let ttl = self.ttl?;
+        let key = (endpoint.to_string(), query.to_string());
+
+        self.byte_entries
+            .get(&key)
+            .filter(|&&(stored, _)| stored.elapsed() < ttl)
+            .map(|&(_, ref bytes)| bytes.as_slice())
+    }
+
+    /// Stores `bytes` under `(endpoint, query)`, timestamped with now.
+    ///
+    /// Does nothing if the cache was never given a TTL.
+    pub fn insert_bytes(&mut self, endpoint: &str, query: &str, bytes: Vec<u8>) {
+        if self.ttl.is_some() {
+            self.byte_entries.insert(
+                (endpoint.to_string(), query.to_string()),
+                (Instant::now(), bytes),
+            );
+        }
+    }
+
+    /// Drops every cached entry for `endpoint`, regardless of query string.
+    pub fn invalidate(&mut self, endpoint: &str) {
+        self.entries.retain(|&(ref e, _), _| e != endpoint);
+        self.byte_entries.retain(|&(ref e, _), _| e != endpoint);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn returns_fresh_entries() {
+        let mut cache = ResponseCache::new(Duration::from_secs(60));
+        cache.insert("getArtist", "id=1", Value::from("cached"));
+
+        assert_eq!(cache.get("getArtist", "id=1"), Some(&Value::from("cached")));
+    }
+
+    #[test]
+    fn invalidate_drops_only_matching_endpoint() {
+        let mut cache = ResponseCache::new(Duration::from_secs(60));
+        cache.insert("getArtist", "id=1", Value::from("artist"));
+        cache.insert("getAlbum", "id=1", Value::from("album"));
+
+        cache.invalidate("getArtist");
+
+        assert_eq!(cache.get("getArtist", "id=1"), None);
+        assert_eq!(cache.get("getAlbum", "id=1"), Some(&Value::from("album")));
+    }
+
+    #[test]
+    fn disabled_cache_stores_nothing() {
+        let mut cache = ResponseCache::default();
+        cache.insert("getArtist", "id=1", Value::from("cached"));
+
+        assert_eq!(cache.get("getArtist", "id=1"), None);
+    }
+
+    #[test]
+    fn caches_bytes_separately_from_json() {
+        let mut cache = ResponseCache::new(Duration::from_secs(60));
+        cache.insert_bytes("getCoverArt", "id=ar-1", vec![1, 2, 3]);
+
+        assert_eq!(cache.get_bytes("getCoverArt", "id=ar-1"), Some(&[1, 2, 3][..]));
+
+        cache.invalidate("getCoverArt");
+        assert_eq!(cache.get_bytes("getCoverArt", "id=ar-1"), None);
+    }
+}
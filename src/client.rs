@@ -0,0 +1,205 @@
+use std::cell::RefCell;
+use std::io::Read;
+use std::time::Duration;
+
+use reqwest;
+use serde_json::{self, Value};
+
+use cache::ResponseCache;
+use error::{http_status, Error, Result};
+use query::Query;
+use response::Response;
+
+/// A handle to a Subsonic-compatible server, and the credentials used to
+/// authenticate against it.
+#[derive(Debug)]
+pub struct Client {
+    url: String,
+    username: String,
+    password: String,
+    http: reqwest::Client,
+    cache: Option<RefCell<ResponseCache>>,
+}
+
+impl Client {
+    /// Creates a client for the server at `url`, authenticating as
+    /// `username`/`password`.
+    pub fn new(url: &str, username: &str, password: &str) -> Client {
+        Client {
+            url: url.trim_end_matches('/').to_string(),
+            username: username.to_string(),
+            password: password.to_string(),
+            http: reqwest::Client::new(),
+            cache: None,
+        }
+    }
+
+    /// Enables response caching, holding entries for `ttl` before they're
+    /// considered stale.
+    ///
+    /// Covers both [`get`](#method.get) and
+    /// [`get_bytes`](#method.get_bytes), so large, immutable payloads like
+    /// cover art are cached too.
+    pub fn with_cache(mut self, ttl: Duration) -> Client {
+        self.cache = Some(RefCell::new(ResponseCache::new(ttl)));
+        self
+    }
+
+    /// Drops every cached response for `endpoint`, regardless of query.
+    ///
+    /// Does nothing if caching isn't enabled.
+    pub fn invalidate(&self, endpoint: &str) {
+        if let Some(cache) = self.cache.as_ref() {
+            cache.borrow_mut().invalidate(endpoint);
+        }
+    }
+
+    fn request_url(&self, endpoint: &str, query_string: &str) -> String {
+        format!(
+            "{}/rest/{}?{}&u={}&p={}&f=json",
+            self.url, endpoint, query_string, self.username, self.password,
+        )
+    }
+
+    /// Builds the full request URL for `endpoint` with `query`, including
+    /// authentication parameters.
+    pub fn build_url(&self, endpoint: &str, query: Query) -> Result<String> {
+        Ok(self.request_url(endpoint, &query.build().to_query_string()))
+    }
+
+    /// Performs a request against `endpoint`, returning the decoded value
+    /// of the `subsonic-response`.
+    pub fn get(&self, endpoint: &str, query: Query) -> Result<Value> {
+        let query_string = query.build().to_query_string();
+
+        if let Some(cache) = self.cache.as_ref() {
+            if let Some(value) = cache.borrow().get(endpoint, &query_string) {
+                return Ok(value.clone());
+            }
+        }
+
+        let url = self.request_url(endpoint, &query_string);
+        let mut res = self.http
+            .get(&url)
+            .send()
+            .map_err(|_| Error::Other("request to server failed"))?;
+
+        if !res.status().is_success() {
+            let mut body = String::new();
+            let _ = res.read_to_string(&mut body);
+            return Err(http_status(res.status().as_u16(), &body));
+        }
+
+        let mut body = String::new();
+        res.read_to_string(&mut body)
+            .map_err(|_| Error::Other("failed to read response body"))?;
+        let value = serde_json::from_str::<Response>(&body)?.into_value()?;
+
+        if let Some(cache) = self.cache.as_ref() {
+            cache.borrow_mut().insert(endpoint, &query_string, value.clone());
+        }
+
+        Ok(value)
+    }
+
+    /// Performs a request against `endpoint`, returning the raw response
+    /// body. Used for binary payloads like cover art or streamed audio.
+    pub fn get_bytes(&self, endpoint: &str, query: Query) -> Result<Vec<u8>> {
+        let query_string = query.build().to_query_string();
+
+        if let Some(cache) = self.cache.as_ref() {
+            if let Some(bytes) = cache.borrow().get_bytes(endpoint, &query_string) {
+                return Ok(bytes.to_vec());
+            }
+        }
+
+        let url = self.request_url(endpoint, &query_string);
+        let mut res = self.http
+            .get(&url)
+            .send()
+            .map_err(|_| Error::Other("request to server failed"))?;
+
+        if !res.status().is_success() {
+            let mut body = String::new();
+            let _ = res.read_to_string(&mut body);
+            return Err(http_status(res.status().as_u16(), &body));
+        }
+
+        let mut bytes = Vec::new();
+        res.read_to_end(&mut bytes)
+            .map_err(|_| Error::Other("failed to read response body"))?;
+
+        if let Some(cache) = self.cache.as_ref() {
+            cache.borrow_mut().insert_bytes(endpoint, &query_string, bytes.clone());
+        }
+
+        Ok(bytes)
+    }
+
+    /// Seeds the cache with a value for `(endpoint, query)`, as if it had
+    /// already been fetched. Test-only, so tests can assert `get` returns
+    /// it without making a real request. Only the JSON cache is seeded;
+    /// `get_bytes` does not read it.
+    #[cfg(test)]
+    fn prime_cache(&self, endpoint: &str, query_string: &str, value: Value) {
+        self.cache
+            .as_ref()
+            .expect("with_cache must be called before priming the cache")
+            .borrow_mut()
+            .insert(endpoint, query_string, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A host reserved by RFC 2606 that never resolves, so a request that
+    /// actually reaches the network fails fast and deterministically.
+    const UNREACHABLE_URL: &str = "http://host.invalid";
+
+    #[test]
+    fn get_is_served_from_cache_without_a_real_request() {
+        let client = Client::new(UNREACHABLE_URL, "user", "pass").with_cache(Duration::from_secs(60));
+        let query = Query::with("id", "1").build();
+        client.prime_cache("getArtist", &query.to_query_string(), Value::from("cached artist"));
+
+        let value = client.get("getArtist", query).unwrap();
+        assert_eq!(value, Value::from("cached artist"));
+    }
+
+    #[test]
+    fn invalidate_removes_the_cached_entry() {
+        let client = Client::new(UNREACHABLE_URL, "user", "pass").with_cache(Duration::from_secs(60));
+        let query = Query::with("id", "1").build();
+        client.prime_cache("getArtist", &query.to_query_string(), Value::from("cached artist"));
+
+        client.invalidate("getArtist");
+
+        // With the cache entry gone, `get` falls through to a real request
+        // against a host that can never resolve, so it must fail.
+        let result = client.get("getArtist", query);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn get_bytes_is_served_from_cache_without_a_real_request() {
+        let client = Client::new(UNREACHABLE_URL, "user", "pass").with_cache(Duration::from_secs(60));
+        let query = Query::with("id", "ar-1").build();
+        client
+            .cache
+            .as_ref()
+            .unwrap()
+            .borrow_mut()
+            .insert_bytes("getCoverArt", &query.to_query_string(), vec![1, 2, 3]);
+
+        let bytes = client.get_bytes("getCoverArt", query).unwrap();
+        assert_eq!(bytes, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn caching_is_opt_in() {
+        let client = Client::new(UNREACHABLE_URL, "user", "pass");
+        assert!(client.cache.is_none());
+    }
+}
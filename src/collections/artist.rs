@@ -1,15 +1,19 @@
+use std::collections::{HashSet, VecDeque};
 use std::result;
 
 use serde::de::{Deserialize, Deserializer};
 use serde_json;
 
 use {Album, Client, Error, Media, Result, Song};
+use id::{ArtistId, SongId};
+use mbid::Mbid;
+use musicbrainz::MusicBrainzLookup;
 use query::Query;
 
 /// Basic information about an artist.
 #[derive(Debug)]
 pub struct Artist {
-    pub id: u64,
+    pub id: ArtistId,
     pub name: String,
     cover_id: Option<String>,
     albums: Vec<Album>,
@@ -21,8 +25,10 @@ pub struct Artist {
 pub struct ArtistInfo {
     /// A blurb about the artist.
     pub biography: String,
-    /// The artist's [MusicBrainz](https://musicbrainz.org/) ID.
-    pub musicbrainz_id: String,
+    /// The artist's [MusicBrainz](https://musicbrainz.org/) ID, if the
+    /// server has one on file (servers without last.fm integration
+    /// usually don't).
+    pub musicbrainz_id: Option<Mbid>,
     /// The artist's [last.fm](https://last.fm) landing page.
     pub lastfm_url: String,
     /// URLs for the artist's image; available in small, medium, and large.
@@ -34,7 +40,7 @@ pub struct ArtistInfo {
 /// An artist suggested by last.fm.
 #[derive(Debug)]
 pub struct SimilarArtist {
-    id: u64,
+    id: ArtistId,
     /// The artist's name.
     pub name: String,
     cover_art: Option<String>,
@@ -50,7 +56,7 @@ impl<'de> Deserialize<'de> for SimilarArtist {
         #[derive(Debug, Deserialize)]
         #[serde(rename_all = "camelCase")]
         struct _SimilarArtist {
-            id: String,
+            id: ArtistId,
             name: String,
             cover_art: Option<String>,
             album_count: String,
@@ -59,10 +65,12 @@ impl<'de> Deserialize<'de> for SimilarArtist {
         let raw = _SimilarArtist::deserialize(de)?;
 
         Ok(SimilarArtist {
-            id: raw.id.parse().unwrap(),
+            id: raw.id,
             name: raw.name,
             cover_art: raw.cover_art,
-            album_count: raw.album_count.parse().unwrap(),
+            album_count: raw.album_count
+                .parse()
+                .map_err(::serde::de::Error::custom)?,
         })
     }
 }
@@ -71,7 +79,7 @@ impl Artist {
     /// Returns a list of albums released by the artist.
     pub fn albums(&self, client: &Client) -> Result<Vec<Album>> {
         if self.albums.len() as u64 != self.album_count {
-            Ok(get_artist(client, self.id)?.albums)
+            Ok(get_artist(client, &self.id)?.albums)
         } else {
             Ok(self.albums.clone())
         }
@@ -91,7 +99,7 @@ impl Artist {
         B: Into<Option<bool>>,
         U: Into<Option<usize>>,
     {
-        let args = Query::with("id", self.id)
+        let args = Query::with("id", self.id.as_str())
             .arg("count", count.into())
             .arg("includeNotPresent", include_not_present.into())
             .build();
@@ -104,13 +112,117 @@ impl Artist {
     where
         U: Into<Option<usize>>,
     {
-        let args = Query::with("id", self.id)
+        let args = Query::with("id", self.id.as_str())
             .arg("count", count.into())
             .build();
 
         let song = client.get("getTopSongs", args)?;
         Ok(get_list_as!(song, Song))
     }
+
+    /// Queries last.fm (via `getSimilarSongs2`) for up to `count` songs
+    /// similar to this artist, seeding an artist radio station.
+    pub fn radio<U>(&self, client: &Client, count: U) -> Result<Vec<Song>>
+    where
+        U: Into<Option<usize>>,
+    {
+        let args = Query::with("id", self.id.as_str())
+            .arg("count", count.into())
+            .build();
+
+        let song = client.get("getSimilarSongs2", args)?;
+        Ok(get_list_as!(song, Song))
+    }
+
+    /// Returns an effectively endless stream of songs similar to this
+    /// artist, requesting further batches from `getSimilarSongs2` as the
+    /// internal queue runs dry.
+    pub fn radio_stream<'a>(&self, client: &'a Client, batch_size: usize) -> RadioStream<'a> {
+        RadioStream::new(client, self.id.clone(), batch_size)
+    }
+
+    /// Cross-references this artist against MusicBrainz, returning the
+    /// release groups `lookup` resolves for its `Mbid`.
+    ///
+    /// Fetches [`info`](#method.info) first to learn the artist's `Mbid`;
+    /// fails if the server has none on file.
+    pub fn musicbrainz_lookup<L: MusicBrainzLookup>(
+        &self,
+        client: &Client,
+        lookup: &L,
+    ) -> Result<Vec<Album>> {
+        let info = self.info(client, None, None)?;
+        let mbid = info.musicbrainz_id
+            .ok_or_else(|| Error::Other("artist has no MusicBrainz ID"))?;
+        lookup.lookup_release_groups(&mbid)
+    }
+}
+
+/// An endless stream of songs similar to an artist.
+///
+/// Built on top of `getSimilarSongs2`, whose `id` parameter the Subsonic
+/// API documents as an artist ID only (unlike the song/album/artist
+/// flexible `getSimilarSongs`). So every refill re-seeds with the artist
+/// itself, and already-seen songs are filtered out client-side, so the
+/// station keeps advancing instead of looping the same fixed batch
+/// forever. A caller pulls a continuous queue of recommendations with
+/// [`Iterator::next`]; the stream ends once the server has nothing new
+/// left to offer.
+pub struct RadioStream<'a> {
+    client: &'a Client,
+    id: ArtistId,
+    batch_size: usize,
+    queue: VecDeque<Song>,
+    seen: HashSet<SongId>,
+}
+
+impl<'a> RadioStream<'a> {
+    fn new(client: &'a Client, id: ArtistId, batch_size: usize) -> RadioStream<'a> {
+        RadioStream {
+            client,
+            id,
+            batch_size,
+            queue: VecDeque::new(),
+            seen: HashSet::new(),
+        }
+    }
+
+    fn refill(&mut self) -> Result<()> {
+        let args = Query::with("id", self.id.as_str())
+            .arg("count", self.batch_size)
+            .build();
+
+        let song = self.client.get("getSimilarSongs2", args)?;
+        let songs: Vec<Song> = get_list_as!(song, Song);
+
+        self.queue = dedupe_unseen(songs, &mut self.seen).into();
+        Ok(())
+    }
+}
+
+impl<'a> Iterator for RadioStream<'a> {
+    type Item = Result<Song>;
+
+    fn next(&mut self) -> Option<Result<Song>> {
+        if self.queue.is_empty() {
+            if let Err(e) = self.refill() {
+                return Some(Err(e));
+            }
+
+            if self.queue.is_empty() {
+                return None;
+            }
+        }
+
+        self.queue.pop_front().map(Ok)
+    }
+}
+
+/// Filters `songs` down to the ones not already recorded in `seen`,
+/// recording the survivors as a side effect so they're skipped on the
+/// next call.
+fn dedupe_unseen(songs: Vec<Song>, seen: &mut HashSet<SongId>) -> Vec<Song> {
+    songs.into_iter().filter(|s| seen.insert(s.id.clone())).collect()
 }
 
 impl<'de> Deserialize<'de> for Artist {
@@ -121,7 +233,7 @@ impl<'de> Deserialize<'de> for Artist {
         #[derive(Debug, Deserialize)]
         #[serde(rename_all = "camelCase")]
         struct _Artist {
-            id: String,
+            id: ArtistId,
             name: String,
             cover_art: Option<String>,
             album_count: u64,
@@ -132,7 +244,7 @@ impl<'de> Deserialize<'de> for Artist {
         let raw = _Artist::deserialize(de)?;
 
         Ok(Artist {
-            id: raw.id.parse().unwrap(),
+            id: raw.id,
             name: raw.name,
             cover_id: raw.cover_art,
             album_count: raw.album_count,
@@ -182,6 +294,7 @@ impl<'de> Deserialize<'de> for ArtistInfo {
         #[serde(rename_all = "camelCase")]
         struct _ArtistInfo {
             biography: String,
+            #[serde(default)]
             music_brainz_id: String,
             last_fm_url: String,
             small_image_url: String,
@@ -192,9 +305,16 @@ impl<'de> Deserialize<'de> for ArtistInfo {
 
         let raw = _ArtistInfo::deserialize(de)?;
 
+        let musicbrainz_id = if raw.music_brainz_id.is_empty() {
+            None
+        } else {
+            use std::convert::TryFrom;
+            Some(Mbid::try_from(raw.music_brainz_id).map_err(::serde::de::Error::custom)?)
+        };
+
         Ok(ArtistInfo {
             biography: raw.biography,
-            musicbrainz_id: raw.music_brainz_id,
+            musicbrainz_id,
             lastfm_url: raw.last_fm_url,
             image_urls: (
                 raw.small_image_url,
@@ -235,30 +355,49 @@ impl Media for SimilarArtist {
 impl SimilarArtist {
     /// Queries the Subsonic server to return full information about the artist.
     pub fn into_artist(self, client: &Client) -> Result<Artist> {
-        self::get_artist(client, self.id)
+        self::get_artist(client, &self.id)
     }
 }
 
 /// Fetches an artist from the Subsonic server.
-fn get_artist(client: &Client, id: u64) -> Result<Artist> {
-    let res = client.get("getArtist", Query::with("id", id))?;
+fn get_artist(client: &Client, id: &ArtistId) -> Result<Artist> {
+    let res = client.get("getArtist", Query::with("id", id.as_str()))?;
     Ok(serde_json::from_value::<Artist>(res)?)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use musicbrainz::NullMusicBrainz;
     use test_util;
 
     #[test]
     fn parse_artist() {
         let parsed = serde_json::from_value::<Artist>(raw()).unwrap();
 
-        assert_eq!(parsed.id, 1);
+        assert_eq!(parsed.id.as_str(), "1");
         assert_eq!(parsed.name, String::from("Misteur Valaire"));
         assert_eq!(parsed.album_count, 1);
     }
 
+    #[test]
+    fn parses_non_numeric_ids_without_panicking() {
+        // Navidrome/Airsonic-style servers hand out hashes or UUIDs instead
+        // of the reference server's small integers; `raw.id.parse().unwrap()`
+        // used to panic on these.
+        let raw: serde_json::Value = serde_json::from_str(
+            r#"{
+            "id" : "ar-1",
+            "name" : "Misteur Valaire",
+            "coverArt" : "ar-1",
+            "albumCount" : 0
+        }"#,
+        ).unwrap();
+
+        let parsed = serde_json::from_value::<Artist>(raw).unwrap();
+        assert_eq!(parsed.id.as_str(), "ar-1");
+    }
+
     #[test]
     fn parse_artist_deep() {
         let parsed = serde_json::from_value::<Artist>(raw()).unwrap();
@@ -290,6 +429,134 @@ mod tests {
         assert!(!cover.is_empty())
     }
 
+    #[test]
+    fn remote_artist_radio() {
+        let mut srv = test_util::demo_site().unwrap();
+        let parsed = serde_json::from_value::<Artist>(raw()).unwrap();
+
+        let songs = parsed.radio(&mut srv, 2).unwrap();
+        assert!(!songs.is_empty());
+    }
+
+    #[test]
+    fn similar_artist_parses_non_numeric_ids_without_panicking() {
+        let raw: serde_json::Value = serde_json::from_str(
+            r#"{
+            "id" : "ar-1",
+            "name" : "Chinese Man",
+            "coverArt" : "ar-1",
+            "albumCount" : "3"
+        }"#,
+        ).unwrap();
+
+        let parsed = serde_json::from_value::<SimilarArtist>(raw).unwrap();
+        assert_eq!(parsed.id.as_str(), "ar-1");
+    }
+
+    #[test]
+    fn dedupe_unseen_drops_songs_already_returned() {
+        let mut seen = HashSet::new();
+        let a = test_song("a");
+        let b = test_song("b");
+
+        let first_batch = dedupe_unseen(vec![a.clone(), b.clone()], &mut seen);
+        assert_eq!(first_batch.len(), 2);
+
+        let second_batch = dedupe_unseen(vec![a, b, test_song("c")], &mut seen);
+        assert_eq!(
+            second_batch.iter().map(|s| s.id.as_str()).collect::<Vec<_>>(),
+            vec!["c"],
+            "songs already seen in a prior batch must not repeat"
+        );
+    }
+
+    #[test]
+    fn remote_artist_radio_stream_does_not_repeat_within_a_session() {
+        let mut srv = test_util::demo_site().unwrap();
+        let parsed = serde_json::from_value::<Artist>(raw()).unwrap();
+
+        let songs: Vec<Song> = parsed
+            .radio_stream(&mut srv, 2)
+            .take(4)
+            .filter_map(Result::ok)
+            .collect();
+
+        let mut ids: Vec<&str> = songs.iter().map(|s| s.id.as_str()).collect();
+        let len_before_dedup = ids.len();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), len_before_dedup);
+    }
+
+    #[test]
+    fn artist_info_treats_an_empty_music_brainz_id_as_absent() {
+        let raw: serde_json::Value = serde_json::from_str(
+            r#"{
+            "biography" : "",
+            "musicBrainzId" : "",
+            "lastFmUrl" : "",
+            "smallImageUrl" : "",
+            "mediumImageUrl" : "",
+            "largeImageUrl" : "",
+            "similarArtist" : []
+        }"#,
+        ).unwrap();
+
+        let parsed = serde_json::from_value::<ArtistInfo>(raw).unwrap();
+        assert_eq!(parsed.musicbrainz_id, None);
+    }
+
+    #[test]
+    fn artist_info_rejects_a_malformed_music_brainz_id() {
+        let raw: serde_json::Value = serde_json::from_str(
+            r#"{
+            "biography" : "",
+            "musicBrainzId" : "not-a-uuid",
+            "lastFmUrl" : "",
+            "smallImageUrl" : "",
+            "mediumImageUrl" : "",
+            "largeImageUrl" : "",
+            "similarArtist" : []
+        }"#,
+        ).unwrap();
+
+        assert!(serde_json::from_value::<ArtistInfo>(raw).is_err());
+    }
+
+    #[test]
+    fn null_music_brainz_never_finds_release_groups() {
+        use std::convert::TryFrom;
+
+        let mbid = Mbid::try_from("f27ec8db-af05-4f36-916e-3d57f91ecf5e").unwrap();
+        let groups = NullMusicBrainz.lookup_release_groups(&mbid).unwrap();
+        assert!(groups.is_empty());
+    }
+
+    #[test]
+    fn remote_musicbrainz_lookup_fails_without_a_music_brainz_id() {
+        // The demo server's reference artist has no MusicBrainz ID on file,
+        // so the lookup should fail fast rather than calling into `lookup`.
+        let mut srv = test_util::demo_site().unwrap();
+        let parsed = serde_json::from_value::<Artist>(raw()).unwrap();
+
+        let err = parsed
+            .musicbrainz_lookup(&mut srv, &NullMusicBrainz)
+            .unwrap_err();
+
+        match err {
+            Error::Other(msg) => assert_eq!(msg, "artist has no MusicBrainz ID"),
+            other => panic!("expected Error::Other, got {:?}", other),
+        }
+    }
+
+    fn test_song(id: &str) -> Song {
+        Song {
+            id: SongId::from(id),
+            title: String::from("title"),
+            duration: None,
+        }
+    }
+
     fn raw() -> serde_json::Value {
         serde_json::from_str(
             r#"{
@@ -0,0 +1,138 @@
+use std::fmt;
+use std::result;
+
+use serde_json;
+
+use response::Response;
+
+/// The result type used throughout sunk.
+pub type Result<T> = result::Result<T, Error>;
+
+/// An error reported by the Subsonic API itself, as described by the
+/// [`error`](http://www.subsonic.org/pages/api.jsp) field of a
+/// `subsonic-response`.
+#[derive(Debug, Deserialize)]
+pub struct ApiError {
+    pub code: u32,
+    pub message: String,
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({})", self.message, self.code)
+    }
+}
+
+/// The error type for all fallible operations in sunk.
+#[derive(Debug)]
+pub enum Error {
+    /// A catch-all for errors without a dedicated variant.
+    Other(&'static str),
+    /// The server understood the request but rejected it at the Subsonic
+    /// level.
+    Api(ApiError),
+    /// The HTTP request itself failed (a non-2xx status). Carries the
+    /// status code and, when the body decoded as a `subsonic-response`,
+    /// its error message; otherwise a generic fallback.
+    HttpStatus(u16, String),
+    /// The response was a well-formed `subsonic-response` envelope, but
+    /// didn't carry any of the fields `Response::into_value` knows how to
+    /// extract.
+    UnrecognizedResponse { status: String, version: String },
+    /// Failed to deserialize a response body.
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Other(msg) => write!(f, "{}", msg),
+            Error::Api(ref err) => write!(f, "{}", err),
+            Error::HttpStatus(code, ref msg) => write!(f, "HTTP {}: {}", code, msg),
+            Error::UnrecognizedResponse {
+                ref status,
+                ref version,
+            } => write!(
+                f,
+                "unrecognized subsonic-response (status {}, version {})",
+                status, version
+            ),
+            Error::Json(ref err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl ::std::error::Error for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::Other(msg) => msg,
+            Error::Api(_) => "Subsonic API error",
+            Error::HttpStatus(..) => "HTTP error",
+            Error::UnrecognizedResponse { .. } => "unrecognized response",
+            Error::Json(ref err) => ::std::error::Error::description(err),
+        }
+    }
+}
+
+impl From<ApiError> for Error {
+    fn from(err: ApiError) -> Error {
+        Error::Api(err)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Error {
+        Error::Json(err)
+    }
+}
+
+/// Builds an [`Error::HttpStatus`](enum.Error.html#variant.HttpStatus) from
+/// a failed HTTP response, decoding the server's
+/// `subsonic-response.error.message` when `body` is a valid Subsonic
+/// envelope and falling back to a generic message otherwise.
+pub fn http_status(status: u16, body: &str) -> Error {
+    let message = serde_json::from_str::<Response>(body)
+        .ok()
+        .and_then(Response::into_error)
+        .map(|err| match err {
+            Error::Api(api) => api.message,
+            other => other.to_string(),
+        })
+        .unwrap_or_else(|| format!("server returned HTTP {}", status));
+
+    Error::HttpStatus(status, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_the_servers_error_message_from_a_subsonic_envelope() {
+        let body = r#"{"subsonic-response": {
+            "status": "failed",
+            "version": "1.14.0",
+            "error": {
+                "code": 70,
+                "message": "Requested resource not found"
+            }
+        }}"#;
+
+        match http_status(404, body) {
+            Error::HttpStatus(404, message) => {
+                assert_eq!(message, "Requested resource not found")
+            }
+            other => panic!("expected HttpStatus, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn falls_back_to_a_generic_message_when_the_body_is_not_json() {
+        match http_status(500, "<html>Internal Server Error</html>") {
+            Error::HttpStatus(500, message) => {
+                assert_eq!(message, "server returned HTTP 500")
+            }
+            other => panic!("expected HttpStatus, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+
+/// Declares a string-backed resource ID newtype.
+///
+/// Subsonic's reference server hands out small integers for `id` fields,
+/// but servers like Navidrome or Airsonic hand out UUIDs or hashes
+/// instead, so these IDs are kept as opaque strings rather than parsed
+/// into a `u64` (which would panic on anything non-numeric).
+macro_rules! id_type {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub struct $name(Box<str>);
+
+        impl $name {
+            /// Returns a borrowed view of the ID, suitable for building a
+            /// [`Query`](../query/struct.Query.html) without cloning.
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = Infallible;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Ok($name(s.into()))
+            }
+        }
+
+        impl<'a> From<&'a str> for $name {
+            fn from(s: &'a str) -> Self {
+                $name(s.into())
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(s: String) -> Self {
+                $name(s.into_boxed_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D>(de: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                let raw = String::deserialize(de)?;
+                Ok($name(raw.into_boxed_str()))
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S>(&self, ser: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                ser.serialize_str(&self.0)
+            }
+        }
+    };
+}
+
+id_type!(
+    /// The unique identifier of an [`Artist`](../collections/artist/struct.Artist.html).
+    ArtistId
+);
+id_type!(
+    /// The unique identifier of an [`Album`](../collections/album/struct.Album.html).
+    AlbumId
+);
+id_type!(
+    /// The unique identifier of a [`Song`](../song/struct.Song.html).
+    SongId
+);
@@ -0,0 +1,97 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use serde::de::{Deserialize, Deserializer};
+
+use error::Error;
+use Result;
+
+/// A [MusicBrainz](https://musicbrainz.org/) identifier: a UUID naming an
+/// entity (artist, release group, recording, ...) in the MusicBrainz
+/// database.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Mbid(Box<str>);
+
+impl Mbid {
+    /// Returns a borrowed view of the raw UUID.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the canonical MusicBrainz artist page for this ID.
+    pub fn url(&self) -> String {
+        format!("https://musicbrainz.org/artist/{}", self.0)
+    }
+}
+
+impl fmt::Display for Mbid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Mbid {
+    type Error = Error;
+
+    fn try_from(s: &'a str) -> Result<Self> {
+        if is_uuid(s) {
+            Ok(Mbid(s.into()))
+        } else {
+            Err(Error::Other("malformed MusicBrainz ID"))
+        }
+    }
+}
+
+impl TryFrom<String> for Mbid {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<Self> {
+        if is_uuid(&s) {
+            Ok(Mbid(s.into_boxed_str()))
+        } else {
+            Err(Error::Other("malformed MusicBrainz ID"))
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Mbid {
+    fn deserialize<D>(de: D) -> ::std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = String::deserialize(de)?;
+        Mbid::try_from(raw).map_err(::serde::de::Error::custom)
+    }
+}
+
+/// Checks that `s` is shaped like a UUID (`8-4-4-4-12` hex digit groups).
+fn is_uuid(s: &str) -> bool {
+    let groups: Vec<&str> = s.split('-').collect();
+    let lens = [8, 4, 4, 4, 12];
+
+    groups.len() == lens.len()
+        && groups
+            .iter()
+            .zip(&lens)
+            .all(|(g, &len)| g.len() == len && g.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_valid_uuid() {
+        let id = Mbid::try_from("f27ec8db-af05-4f36-916e-3d57f91ecf5e").unwrap();
+        assert_eq!(id.as_str(), "f27ec8db-af05-4f36-916e-3d57f91ecf5e");
+        assert_eq!(
+            id.url(),
+            "https://musicbrainz.org/artist/f27ec8db-af05-4f36-916e-3d57f91ecf5e"
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_uuid() {
+        assert!(Mbid::try_from("not-a-uuid").is_err());
+    }
+}
@@ -0,0 +1,27 @@
+use mbid::Mbid;
+use {Album, Result};
+
+/// A pluggable lookup against the [MusicBrainz](https://musicbrainz.org/)
+/// database.
+///
+/// The Subsonic API only ever hands back a bare [`Mbid`](../mbid/struct.Mbid.html);
+/// actually resolving it against MusicBrainz (or a local mirror, or a test
+/// double) is left to the implementor, so this isn't hardwired to any one
+/// backend.
+pub trait MusicBrainzLookup {
+    /// Returns the release groups MusicBrainz associates with `mbid`.
+    fn lookup_release_groups(&self, mbid: &Mbid) -> Result<Vec<Album>>;
+}
+
+/// A [`MusicBrainzLookup`](trait.MusicBrainzLookup.html) that never talks to
+/// the network.
+///
+/// Useful as a default when no MusicBrainz integration is configured.
+#[derive(Debug, Default)]
+pub struct NullMusicBrainz;
+
+impl MusicBrainzLookup for NullMusicBrainz {
+    fn lookup_release_groups(&self, _mbid: &Mbid) -> Result<Vec<Album>> {
+        Ok(Vec::new())
+    }
+}
@@ -0,0 +1,62 @@
+use std::collections::BTreeMap;
+
+/// A builder for a Subsonic request's query-string arguments.
+///
+/// Keeps arguments sorted by key so the same logical query always
+/// serializes to the same string, which lets [`ResponseCache`][cache] use
+/// it as a cache key.
+///
+/// [cache]: ../cache/struct.ResponseCache.html
+#[derive(Debug, Clone, Default)]
+pub struct Query {
+    args: BTreeMap<String, String>,
+}
+
+impl Query {
+    /// Starts a query with a single required argument.
+    pub fn with<V: ToString>(key: &str, value: V) -> Query {
+        Query::default().arg(key, Some(value))
+    }
+
+    /// Adds an optional argument, doing nothing if `value` is `None`.
+    pub fn arg<V: ToString>(mut self, key: &str, value: impl Into<Option<V>>) -> Query {
+        if let Some(value) = value.into() {
+            self.args.insert(key.to_string(), value.to_string());
+        }
+        self
+    }
+
+    /// Finalizes the query. A no-op today, kept as the usual terminal call
+    /// in a `Query` builder chain so call sites read consistently.
+    pub fn build(self) -> Query {
+        self
+    }
+
+    /// Serializes the query as a `&`-joined `key=value` string.
+    pub fn to_query_string(&self) -> String {
+        self.args
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<_>>()
+            .join("&")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn omits_none_arguments() {
+        let query = Query::with("id", "1").arg::<&str>("count", None).build();
+
+        assert_eq!(query.to_query_string(), "id=1");
+    }
+
+    #[test]
+    fn orders_arguments_by_key() {
+        let query = Query::with("id", "1").arg("count", Some(5)).build();
+
+        assert_eq!(query.to_query_string(), "count=5&id=1");
+    }
+}
@@ -130,7 +130,10 @@ impl Response {
         maybe!(play_queue);
         maybe!(scan_status);
 
-        Err(Error::Other("non-exhaustive `into_value()`"))
+        Err(Error::UnrecognizedResponse {
+            status: self.inner.status,
+            version: self.inner.version,
+        })
     }
 
     /// Extracts the error struct of the response. Returns `None` if the
@@ -0,0 +1,105 @@
+use serde::de::{Deserialize, Deserializer};
+
+use id::SongId;
+use query::Query;
+use stream::StreamOptions;
+use {Client, Result};
+
+/// A single track on the Subsonic server.
+#[derive(Debug, Clone)]
+pub struct Song {
+    pub id: SongId,
+    pub title: String,
+    pub duration: Option<u32>,
+}
+
+impl<'de> Deserialize<'de> for Song {
+    fn deserialize<D>(de: D) -> ::std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Debug, Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct _Song {
+            id: SongId,
+            title: String,
+            duration: Option<u32>,
+        }
+
+        let raw = _Song::deserialize(de)?;
+
+        Ok(Song {
+            id: raw.id,
+            title: raw.title,
+            duration: raw.duration,
+        })
+    }
+}
+
+impl Song {
+    /// Builds this song's stream URL, folding in `opts`'s format, bitrate,
+    /// and time offset when present so the server can transcode on the
+    /// fly instead of always serving the original file.
+    pub fn stream_url(&self, client: &Client, opts: StreamOptions) -> Result<String> {
+        let query = opts.apply(Query::with("id", self.id.as_str())).build();
+        client.build_url("stream", query)
+    }
+
+    /// Streams this song's audio bytes, honoring `opts` the same way as
+    /// [`stream_url`](#method.stream_url).
+    pub fn stream(&self, client: &Client, opts: StreamOptions) -> Result<Vec<u8>> {
+        let query = opts.apply(Query::with("id", self.id.as_str())).build();
+        client.get_bytes("stream", query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stream::Format;
+    use test_util;
+
+    fn test_song() -> Song {
+        Song {
+            id: SongId::from("1"),
+            title: String::from("Bellevue"),
+            duration: Some(240),
+        }
+    }
+
+    #[test]
+    fn stream_url_emits_format_bitrate_and_offset() {
+        let client = Client::new("http://demo.subsonic.org", "guest3", "guest3");
+        let opts = StreamOptions::new()
+            .format(Format::Opus)
+            .max_bitrate(128)
+            .time_offset(30);
+
+        let url = test_song().stream_url(&client, opts).unwrap();
+
+        assert!(url.contains("format=opus"));
+        assert!(url.contains("maxBitRate=128"));
+        assert!(url.contains("timeOffset=30"));
+        assert!(url.contains("id=1"));
+    }
+
+    #[test]
+    fn stream_url_with_no_options_omits_them() {
+        let client = Client::new("http://demo.subsonic.org", "guest3", "guest3");
+
+        let url = test_song().stream_url(&client, StreamOptions::new()).unwrap();
+
+        assert!(!url.contains("format="));
+        assert!(!url.contains("maxBitRate="));
+        assert!(!url.contains("timeOffset="));
+    }
+
+    #[test]
+    fn remote_song_stream() {
+        let mut srv = test_util::demo_site().unwrap();
+        let song = test_song();
+
+        let bytes = song.stream(&mut srv, StreamOptions::new()).unwrap();
+        assert!(!bytes.is_empty());
+    }
+}
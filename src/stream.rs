@@ -0,0 +1,119 @@
+use query::Query;
+
+/// A container/codec the Subsonic server can transcode audio into while
+/// streaming, mirroring the values accepted by the `stream` endpoint's
+/// `format` parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Mp3,
+    Opus,
+    Aac,
+    Flac,
+    /// Streams the original file untouched, skipping transcoding.
+    Raw,
+}
+
+impl Format {
+    fn as_str(&self) -> &'static str {
+        match *self {
+            Format::Mp3 => "mp3",
+            Format::Opus => "opus",
+            Format::Aac => "aac",
+            Format::Flac => "flac",
+            Format::Raw => "raw",
+        }
+    }
+}
+
+/// Options controlling how a song is streamed: its target format, maximum
+/// bitrate, and where in the track to start.
+///
+/// Passed to [`Song::stream`](../song/struct.Song.html#method.stream) and
+/// [`Song::stream_url`](../song/struct.Song.html#method.stream_url) so
+/// bandwidth-constrained clients can ask the server to transcode, rather
+/// than always pulling the original file.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StreamOptions {
+    pub format: Option<Format>,
+    pub max_bitrate: Option<u32>,
+    pub time_offset: Option<u32>,
+}
+
+impl StreamOptions {
+    /// An empty set of options: stream the original file from the start.
+    pub fn new() -> StreamOptions {
+        StreamOptions::default()
+    }
+
+    pub fn format(mut self, format: Format) -> StreamOptions {
+        self.format = Some(format);
+        self
+    }
+
+    pub fn max_bitrate(mut self, max_bitrate: u32) -> StreamOptions {
+        self.max_bitrate = Some(max_bitrate);
+        self
+    }
+
+    pub fn time_offset(mut self, time_offset: u32) -> StreamOptions {
+        self.time_offset = Some(time_offset);
+        self
+    }
+
+    /// Folds these options into `query` as the Subsonic `format`,
+    /// `maxBitRate`, and `timeOffset` arguments.
+    pub(crate) fn apply(&self, query: Query) -> Query {
+        query
+            .arg("format", self.format.map(|f| f.as_str()))
+            .arg("maxBitRate", self.max_bitrate)
+            .arg("timeOffset", self.time_offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_sets_fields() {
+        let opts = StreamOptions::new()
+            .format(Format::Opus)
+            .max_bitrate(128)
+            .time_offset(30);
+
+        assert_eq!(opts.format, Some(Format::Opus));
+        assert_eq!(opts.max_bitrate, Some(128));
+        assert_eq!(opts.time_offset, Some(30));
+    }
+
+    #[test]
+    fn default_is_empty() {
+        let opts = StreamOptions::new();
+
+        assert_eq!(opts.format, None);
+        assert_eq!(opts.max_bitrate, None);
+        assert_eq!(opts.time_offset, None);
+    }
+
+    #[test]
+    fn apply_emits_format_max_bitrate_and_time_offset() {
+        let opts = StreamOptions::new()
+            .format(Format::Opus)
+            .max_bitrate(128)
+            .time_offset(30);
+
+        let query = opts.apply(Query::with("id", "1")).build();
+
+        assert_eq!(
+            query.to_query_string(),
+            "format=opus&id=1&maxBitRate=128&timeOffset=30"
+        );
+    }
+
+    #[test]
+    fn apply_with_no_options_leaves_query_untouched() {
+        let query = StreamOptions::new().apply(Query::with("id", "1")).build();
+
+        assert_eq!(query.to_query_string(), "id=1");
+    }
+}
@@ -0,0 +1,8 @@
+use client::Client;
+use error::Result;
+
+/// Returns a `Client` pointed at the public Subsonic demo server, shared
+/// by integration-style tests across the crate.
+pub fn demo_site() -> Result<Client> {
+    Ok(Client::new("http://demo.subsonic.org", "guest3", "guest3"))
+}